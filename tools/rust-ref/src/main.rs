@@ -1,10 +1,98 @@
 use anyhow::{Context, Result};
 use catalyst_core::protocol::{
-    encode_wire_tx_v1, transaction_signing_payload_v1, tx_id_v1, AggregatedSignature, EntryAmount,
-    Transaction, TransactionCore, TransactionEntry, TransactionType,
+    decode_wire_tx_v1, encode_wire_tx_v1, transaction_signing_payload_v1, tx_id_v1,
+    AggregatedSignature, EntryAmount, Transaction, TransactionCore, TransactionEntry,
+    TransactionType,
 };
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::Deserialize;
 
+const USAGE: &str = "usage: cargo run -- <encode|decode|sign|verify|aggregate> <path/to/v1_vectors.json | wire_hex> [--fee N] [--min-fee N] [--format json|cbor] [secret_key_hex | signature_hex ...]";
+
+/// Output format for a mode's computed artifacts.
+#[derive(Debug, Clone, Copy)]
+enum OutputFormat {
+    /// Pretty-printed JSON (the default).
+    Json,
+    /// Canonical/deterministic CBOR (RFC 8949 §4.2.1): sorted map keys,
+    /// shortest-form integers, definite-length arrays and maps. Written raw
+    /// to stdout so the same transaction hashes to the same bytes across
+    /// runs and languages.
+    Cbor,
+}
+
+/// Pulls `--format json|cbor` out of a positional argument list, defaulting
+/// to JSON, and leaves the remaining positionals in order.
+fn take_format(args: &mut Vec<String>) -> Result<OutputFormat> {
+    let Some(pos) = args.iter().position(|a| a == "--format") else {
+        return Ok(OutputFormat::Json);
+    };
+    let value = args
+        .get(pos + 1)
+        .with_context(|| format!("--format requires a value\n{USAGE}"))?
+        .clone();
+    let format = match value.as_str() {
+        "json" => OutputFormat::Json,
+        "cbor" => OutputFormat::Cbor,
+        other => anyhow::bail!("unknown --format `{other}`, expected json or cbor"),
+    };
+    args.drain(pos..=pos + 1);
+    Ok(format)
+}
+
+/// Converts a `serde_json::Value` into a `ciborium::Value` tree with map
+/// entries sorted into RFC 8949 §4.2.1 canonical order: by the length of
+/// each key's own encoded CBOR bytes, then bytewise lexicographically.
+fn canonical_cbor_value(value: &serde_json::Value) -> ciborium::Value {
+    match value {
+        serde_json::Value::Null => ciborium::Value::Null,
+        serde_json::Value::Bool(b) => ciborium::Value::Bool(*b),
+        serde_json::Value::Number(n) => n
+            .as_u64()
+            .map(|u| ciborium::Value::Integer(u.into()))
+            .or_else(|| n.as_i64().map(|i| ciborium::Value::Integer(i.into())))
+            .unwrap_or_else(|| ciborium::Value::Float(n.as_f64().unwrap_or_default())),
+        serde_json::Value::String(s) => ciborium::Value::Text(s.clone()),
+        serde_json::Value::Array(items) => {
+            ciborium::Value::Array(items.iter().map(canonical_cbor_value).collect())
+        }
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map
+                .iter()
+                .map(|(k, v)| (ciborium::Value::Text(k.clone()), canonical_cbor_value(v)))
+                .collect();
+            entries.sort_by(|(a, _), (b, _)| canonical_key_order(a, b));
+            ciborium::Value::Map(entries)
+        }
+    }
+}
+
+/// Orders two map keys the way RFC 8949 §4.2.1 canonical CBOR requires,
+/// encoding each key to CBOR first: the one with the shorter encoding sorts
+/// first; ties break by bytewise lexicographic comparison of the encoded bytes.
+fn canonical_key_order(a: &ciborium::Value, b: &ciborium::Value) -> std::cmp::Ordering {
+    let mut a_bytes = Vec::new();
+    let mut b_bytes = Vec::new();
+    ciborium::into_writer(a, &mut a_bytes).expect("encode cbor key");
+    ciborium::into_writer(b, &mut b_bytes).expect("encode cbor key");
+    a_bytes.len().cmp(&b_bytes.len()).then_with(|| a_bytes.cmp(&b_bytes))
+}
+
+/// Emits a mode's computed artifacts in the requested format.
+fn emit(out: &serde_json::Value, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(out)?),
+        OutputFormat::Cbor => {
+            let canonical = canonical_cbor_value(out);
+            let mut bytes = Vec::new();
+            ciborium::into_writer(&canonical, &mut bytes).context("encode canonical cbor")?;
+            std::io::Write::write_all(&mut std::io::stdout(), &bytes)
+                .context("write cbor to stdout")?;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug, Deserialize)]
 struct Vectors {
     chain_id_hex: String,
@@ -14,7 +102,10 @@ struct Vectors {
 
 #[derive(Debug, Deserialize)]
 struct TxVector {
-    tx_type: String,
+    /// Deserialized straight into `catalyst_core::protocol::TransactionType`
+    /// so every variant the protocol defines is supported automatically,
+    /// with no variant list to keep in sync here.
+    tx_type: TransactionType,
     nonce: u64,
     lock_time: u32,
     fees: u64,
@@ -27,7 +118,12 @@ struct TxVector {
 #[derive(Debug, Deserialize)]
 struct EntryVector {
     public_key_hex: String,
-    amount: i64,
+    /// Set for `NonConfidentialTransfer`/`Contract` entries; mutually
+    /// exclusive with `commitment_hex`.
+    amount: Option<i64>,
+    /// Set for `ConfidentialTransfer` entries: the entry's Pedersen
+    /// commitment, in place of a plaintext `amount`.
+    commitment_hex: Option<String>,
 }
 
 fn strip0x(s: &str) -> &str {
@@ -44,36 +140,62 @@ fn decode_hex<const N: usize>(hex_str: &str) -> Result<[u8; N]> {
     Ok(out)
 }
 
-fn main() -> Result<()> {
-    let path = std::env::args()
-        .nth(1)
-        .context("usage: cargo run -- <path/to/v1_vectors.json>")?;
+fn entry_amount_json(amount: &EntryAmount) -> serde_json::Value {
+    match amount {
+        EntryAmount::NonConfidential(v) => serde_json::json!(v),
+        EntryAmount::Confidential(commitment) => {
+            serde_json::json!(format!("0x{}", hex::encode(commitment)))
+        }
+    }
+}
 
-    let raw = std::fs::read_to_string(&path).with_context(|| format!("read {path}"))?;
-    let v: Vectors = serde_json::from_str(&raw).context("parse json vectors")?;
+/// Builds one entry's `EntryAmount` from its vector, enforcing that the
+/// representation matches what `tx_type` declares: `ConfidentialTransfer`
+/// entries carry a commitment, every other type carries a plaintext amount.
+fn entry_amount(tx_type: &TransactionType, i: usize, e: &EntryVector) -> Result<EntryAmount> {
+    match (tx_type, &e.amount, &e.commitment_hex) {
+        (TransactionType::ConfidentialTransfer, None, Some(commitment_hex)) => {
+            let commitment = hex::decode(strip0x(commitment_hex))
+                .with_context(|| format!("entry {i}: decode commitment_hex"))?;
+            Ok(EntryAmount::Confidential(commitment))
+        }
+        (TransactionType::ConfidentialTransfer, None, None) => {
+            anyhow::bail!("entry {i}: ConfidentialTransfer entries require commitment_hex")
+        }
+        (TransactionType::ConfidentialTransfer, Some(_), _) => {
+            anyhow::bail!("entry {i}: ConfidentialTransfer entries must not set amount")
+        }
+        (_, Some(amount), None) => Ok(EntryAmount::NonConfidential(*amount)),
+        (other, None, Some(_)) => {
+            anyhow::bail!("entry {i}: {other:?} entries must use amount, not commitment_hex")
+        }
+        (_, None, None) => anyhow::bail!("entry {i}: missing amount"),
+        (_, Some(_), Some(_)) => {
+            anyhow::bail!("entry {i}: set either amount or commitment_hex, not both")
+        }
+    }
+}
 
+/// Parses the shared `chain_id` / `genesis_hash` / `TransactionCore` out of a
+/// vector file. Every mode that needs to rebuild the signing payload starts here.
+fn parse_core(v: &Vectors) -> Result<(u64, [u8; 32], TransactionCore)> {
     let chain_id = u64::from_str_radix(strip0x(&v.chain_id_hex), 16)
         .with_context(|| format!("parse chain_id_hex {}", v.chain_id_hex))?;
     let genesis_hash = decode_hex::<32>(&v.genesis_hash_hex)?;
 
-    let tx_type = match v.tx.tx_type.as_str() {
-        "NonConfidentialTransfer" => TransactionType::NonConfidentialTransfer,
-        other => anyhow::bail!("unsupported tx_type: {other}"),
-    };
+    let tx_type = v.tx.tx_type;
 
     let data = hex::decode(strip0x(&v.tx.data_hex)).context("decode data_hex")?;
-    let sig = hex::decode(strip0x(&v.tx.signature_hex)).context("decode signature_hex")?;
 
     let entries = v
         .tx
         .entries
         .iter()
-        .map(|e| {
-            Ok(TransactionEntry {
-                public_key: decode_hex::<32>(&e.public_key_hex)?,
-                amount: EntryAmount::NonConfidential(e.amount),
-            })
-        })
+        .enumerate()
+        .map(|(i, e)| Ok(TransactionEntry {
+            public_key: decode_hex::<32>(&e.public_key_hex)?,
+            amount: entry_amount(&tx_type, i, e)?,
+        }))
         .collect::<Result<Vec<_>>>()?;
 
     let core = TransactionCore {
@@ -85,6 +207,110 @@ fn main() -> Result<()> {
         data,
     };
 
+    Ok((chain_id, genesis_hash, core))
+}
+
+fn read_vectors(path: &str) -> Result<Vectors> {
+    let raw = std::fs::read_to_string(path).with_context(|| format!("read {path}"))?;
+    serde_json::from_str(&raw).context("parse json vectors")
+}
+
+/// Pulls a `--flag value` pair out of a positional argument list, leaving the
+/// remaining positionals in order.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> Result<Option<u64>> {
+    let Some(pos) = args.iter().position(|a| a == flag) else {
+        return Ok(None);
+    };
+    let value = args
+        .get(pos + 1)
+        .with_context(|| format!("{flag} requires a value\n{USAGE}"))?
+        .clone();
+    let parsed = value
+        .parse::<u64>()
+        .with_context(|| format!("parse {flag} value: {value}"))?;
+    args.drain(pos..=pos + 1);
+    Ok(Some(parsed))
+}
+
+/// Checks the transaction's economic invariants before it is encoded onto
+/// the wire: `fees` meets the caller's minimum, and, for a
+/// `NonConfidentialTransfer`, the signed sum of entry amounts plus `fees`
+/// nets to zero.
+fn validate_balance(core: &TransactionCore, min_fee: u64) -> Result<()> {
+    if core.fees < min_fee {
+        anyhow::bail!(
+            "fees {} below required minimum fee {min_fee}",
+            core.fees
+        );
+    }
+
+    match core.tx_type {
+        TransactionType::NonConfidentialTransfer => {}
+        _ => return Ok(()),
+    }
+
+    let mut sum: i128 = i128::from(core.fees);
+    let mut amounts = Vec::with_capacity(core.entries.len());
+    for (i, entry) in core.entries.iter().enumerate() {
+        match entry.amount {
+            EntryAmount::NonConfidential(amount) => {
+                sum += i128::from(amount);
+                amounts.push((i, amount));
+            }
+            _ => anyhow::bail!("entry {i}: NonConfidentialTransfer requires a plaintext amount"),
+        }
+    }
+
+    if sum != 0 {
+        let entries = amounts
+            .iter()
+            .map(|(i, amount)| format!("entry {i}: {amount}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        anyhow::bail!(
+            "entries and fees do not net to zero: sum(entry amounts)={}, fees={}, imbalance={sum} ({entries})",
+            sum - i128::from(core.fees),
+            core.fees,
+        );
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let mode = args.next().context(USAGE)?;
+    match mode.as_str() {
+        "encode" => run_encode(args),
+        "decode" => run_decode(args),
+        "sign" => run_sign(args),
+        "verify" => run_verify(args),
+        "aggregate" => run_aggregate(args),
+        other => anyhow::bail!("unknown mode `{other}`\n{USAGE}"),
+    }
+}
+
+fn run_encode(args: impl Iterator<Item = String>) -> Result<()> {
+    let mut args: Vec<String> = args.collect();
+    let min_fee = take_flag(&mut args, "--min-fee")?.unwrap_or(0);
+    let fee_override = take_flag(&mut args, "--fee")?;
+    if fee_override.is_some() {
+        anyhow::bail!(
+            "encode does not support --fee: the vector's signature_hex was produced over the \
+             original fee, and overriding it here would invalidate that signature with no way \
+             to re-sign it; use `sign` or `aggregate` with --fee to produce a new signature \
+             for the new fee"
+        );
+    }
+    let format = take_format(&mut args)?;
+    let mut args = args.into_iter();
+    let path = args.next().context(USAGE)?;
+    let v = read_vectors(&path)?;
+    let (chain_id, genesis_hash, core) = parse_core(&v)?;
+    validate_balance(&core, min_fee)?;
+
+    let sig = hex::decode(strip0x(&v.tx.signature_hex)).context("decode signature_hex")?;
+
     let tx = Transaction {
         core: core.clone(),
         signature: AggregatedSignature(sig),
@@ -104,7 +330,253 @@ fn main() -> Result<()> {
         "tx_id_v1_hex": format!("0x{}", hex::encode(txid)),
     });
 
-    println!("{}", serde_json::to_string_pretty(&out)?);
+    emit(&out, format)?;
+    Ok(())
+}
+
+/// Signs a vector entirely offline: each `secret_key_hex` signs the shared
+/// `transaction_signing_payload_v1` and the per-signer signatures are
+/// concatenated, in order, into the transaction's `AggregatedSignature`.
+fn run_sign(args: impl Iterator<Item = String>) -> Result<()> {
+    let mut args: Vec<String> = args.collect();
+    let min_fee = take_flag(&mut args, "--min-fee")?.unwrap_or(0);
+    let fee_override = take_flag(&mut args, "--fee")?;
+    let format = take_format(&mut args)?;
+    let mut args = args.into_iter();
+    let path = args.next().context(USAGE)?;
+    let secret_keys_hex: Vec<String> = args.collect();
+    if secret_keys_hex.is_empty() {
+        anyhow::bail!("sign requires at least one secret_key_hex\n{USAGE}");
+    }
+
+    let v = read_vectors(&path)?;
+    let (chain_id, genesis_hash, mut core) = parse_core(&v)?;
+    if let Some(fee) = fee_override {
+        core.fees = fee;
+    }
+    validate_balance(&core, min_fee)?;
+
+    if secret_keys_hex.len() != core.entries.len() {
+        anyhow::bail!(
+            "got {} secret keys but {} entries; sign needs exactly one secret_key_hex per entry, in entry order",
+            secret_keys_hex.len(),
+            core.entries.len(),
+        );
+    }
+
+    let signing_payload =
+        transaction_signing_payload_v1(&core, v.tx.timestamp, chain_id, genesis_hash)
+            .map_err(|e| anyhow::anyhow!("signing_payload_v1: {e}"))?;
+
+    let mut sig_bytes = Vec::with_capacity(secret_keys_hex.len() * 64);
+    for key_hex in &secret_keys_hex {
+        let secret = decode_hex::<32>(key_hex)?;
+        let signature = SigningKey::from_bytes(&secret).sign(&signing_payload);
+        sig_bytes.extend_from_slice(&signature.to_bytes());
+    }
+
+    let tx = Transaction {
+        core: core.clone(),
+        signature: AggregatedSignature(sig_bytes),
+        timestamp: v.tx.timestamp,
+    };
+
+    let wire = encode_wire_tx_v1(&tx).map_err(|e| anyhow::anyhow!("encode_wire_tx_v1: {e}"))?;
+    let txid = tx_id_v1(&tx).map_err(|e| anyhow::anyhow!("tx_id_v1: {e}"))?;
+
+    let out = serde_json::json!({
+        "chain_id_u64": chain_id,
+        "signing_payload_v1_hex": format!("0x{}", hex::encode(&signing_payload)),
+        "signature_hex": format!("0x{}", hex::encode(&tx.signature.0)),
+        "wire_tx_v1_hex": format!("0x{}", hex::encode(wire)),
+        "tx_id_v1_hex": format!("0x{}", hex::encode(txid)),
+    });
+
+    emit(&out, format)?;
+    Ok(())
+}
+
+/// Splits an `AggregatedSignature` into one 64-byte Ed25519 signature per
+/// entry (in entry order) and checks each against the shared signing payload
+/// and that entry's public key.
+fn verify_aggregated(
+    core: &TransactionCore,
+    signing_payload: &[u8],
+    sig_bytes: &[u8],
+) -> Result<(bool, Vec<serde_json::Value>)> {
+    if sig_bytes.len() != core.entries.len() * 64 {
+        anyhow::bail!(
+            "aggregated signature has {} bytes, expected {} (64 per entry across {} entries)",
+            sig_bytes.len(),
+            core.entries.len() * 64,
+            core.entries.len(),
+        );
+    }
+
+    let mut all_valid = true;
+    let per_entry = core
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| -> Result<serde_json::Value> {
+            let chunk: [u8; 64] = sig_bytes[i * 64..(i + 1) * 64]
+                .try_into()
+                .expect("checked length above");
+            let signature = Signature::from_bytes(&chunk);
+            let verifying_key = VerifyingKey::from_bytes(&entry.public_key)
+                .with_context(|| format!("entry {i} public key"))?;
+            let valid = verifying_key.verify(signing_payload, &signature).is_ok();
+            all_valid &= valid;
+            Ok(serde_json::json!({
+                "public_key_hex": format!("0x{}", hex::encode(entry.public_key)),
+                "valid": valid,
+            }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((all_valid, per_entry))
+}
+
+/// Verifies `tx.signature_hex` from a vector file against the shared signing
+/// payload and each entry's public key, without needing a node connection.
+fn run_verify(args: impl Iterator<Item = String>) -> Result<()> {
+    let mut args: Vec<String> = args.collect();
+    let format = take_format(&mut args)?;
+    let mut args = args.into_iter();
+    let path = args.next().context(USAGE)?;
+    let v = read_vectors(&path)?;
+    let (chain_id, genesis_hash, core) = parse_core(&v)?;
+
+    let signing_payload =
+        transaction_signing_payload_v1(&core, v.tx.timestamp, chain_id, genesis_hash)
+            .map_err(|e| anyhow::anyhow!("signing_payload_v1: {e}"))?;
+    let sig_bytes = hex::decode(strip0x(&v.tx.signature_hex)).context("decode signature_hex")?;
+    let (all_valid, per_entry) = verify_aggregated(&core, &signing_payload, &sig_bytes)?;
+
+    let out = serde_json::json!({
+        "signing_payload_v1_hex": format!("0x{}", hex::encode(&signing_payload)),
+        "entries": per_entry,
+        "all_valid": all_valid,
+    });
+
+    emit(&out, format)?;
+    Ok(())
+}
+
+/// Combines one per-signer signature per entry (each produced separately over
+/// `transaction_signing_payload_v1`, e.g. on separate hardware signers) into
+/// the single `AggregatedSignature` a coordinator broadcasts, and validates
+/// the result against every entry's public key before emitting the wire tx.
+fn run_aggregate(args: impl Iterator<Item = String>) -> Result<()> {
+    let mut args: Vec<String> = args.collect();
+    let min_fee = take_flag(&mut args, "--min-fee")?.unwrap_or(0);
+    let fee_override = take_flag(&mut args, "--fee")?;
+    let format = take_format(&mut args)?;
+    let mut args = args.into_iter();
+    let path = args.next().context(USAGE)?;
+    let signatures_hex: Vec<String> = args.collect();
+
+    let v = read_vectors(&path)?;
+    let (chain_id, genesis_hash, mut core) = parse_core(&v)?;
+    if let Some(fee) = fee_override {
+        core.fees = fee;
+    }
+    validate_balance(&core, min_fee)?;
+
+    if signatures_hex.len() != core.entries.len() {
+        anyhow::bail!(
+            "got {} signatures but {} entries; aggregate needs exactly one signature per entry, in entry order",
+            signatures_hex.len(),
+            core.entries.len(),
+        );
+    }
+
+    let signing_payload =
+        transaction_signing_payload_v1(&core, v.tx.timestamp, chain_id, genesis_hash)
+            .map_err(|e| anyhow::anyhow!("signing_payload_v1: {e}"))?;
+
+    let mut sig_bytes = Vec::with_capacity(signatures_hex.len() * 64);
+    for sig_hex in &signatures_hex {
+        sig_bytes.extend_from_slice(&decode_hex::<64>(sig_hex)?);
+    }
+
+    let (all_valid, per_entry) = verify_aggregated(&core, &signing_payload, &sig_bytes)?;
+    if !all_valid {
+        let failed: Vec<String> = per_entry
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.get("valid").and_then(serde_json::Value::as_bool) == Some(false))
+            .map(|(i, e)| {
+                let pk = e
+                    .get("public_key_hex")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("?");
+                format!("entry {i} ({pk})")
+            })
+            .collect();
+        anyhow::bail!(
+            "aggregated signature failed verification for {}; refusing to emit a wire tx",
+            failed.join(", "),
+        );
+    }
+
+    let tx = Transaction {
+        core: core.clone(),
+        signature: AggregatedSignature(sig_bytes),
+        timestamp: v.tx.timestamp,
+    };
+    let wire = encode_wire_tx_v1(&tx).map_err(|e| anyhow::anyhow!("encode_wire_tx_v1: {e}"))?;
+    let txid = tx_id_v1(&tx).map_err(|e| anyhow::anyhow!("tx_id_v1: {e}"))?;
+
+    let out = serde_json::json!({
+        "signature_hex": format!("0x{}", hex::encode(&tx.signature.0)),
+        "entries": per_entry,
+        "all_valid": all_valid,
+        "wire_tx_v1_hex": format!("0x{}", hex::encode(wire)),
+        "tx_id_v1_hex": format!("0x{}", hex::encode(txid)),
+    });
+
+    emit(&out, format)?;
+    Ok(())
+}
+
+fn run_decode(args: impl Iterator<Item = String>) -> Result<()> {
+    let mut args: Vec<String> = args.collect();
+    let format = take_format(&mut args)?;
+    let mut args = args.into_iter();
+    let wire_hex = args.next().context(USAGE)?;
+
+    let wire = hex::decode(strip0x(&wire_hex)).context("decode wire hex")?;
+    let tx = decode_wire_tx_v1(&wire).map_err(|e| anyhow::anyhow!("decode_wire_tx_v1: {e}"))?;
+    let txid = tx_id_v1(&tx).map_err(|e| anyhow::anyhow!("tx_id_v1: {e}"))?;
+    let reencoded = encode_wire_tx_v1(&tx).map_err(|e| anyhow::anyhow!("encode_wire_tx_v1: {e}"))?;
+
+    let entries: Vec<_> = tx
+        .core
+        .entries
+        .iter()
+        .map(|e| {
+            serde_json::json!({
+                "public_key_hex": format!("0x{}", hex::encode(e.public_key)),
+                "amount": entry_amount_json(&e.amount),
+            })
+        })
+        .collect();
+
+    let out = serde_json::json!({
+        "tx_type": format!("{:?}", tx.core.tx_type),
+        "nonce": tx.core.nonce,
+        "lock_time": tx.core.lock_time,
+        "fees": tx.core.fees,
+        "entries": entries,
+        "data_hex": format!("0x{}", hex::encode(&tx.core.data)),
+        "timestamp": tx.timestamp,
+        "signature_hex": format!("0x{}", hex::encode(&tx.signature.0)),
+        "tx_id_v1_hex": format!("0x{}", hex::encode(txid)),
+        "reencodes_byte_for_byte": reencoded == wire,
+    });
+
+    emit(&out, format)?;
     Ok(())
 }
 